@@ -15,10 +15,12 @@
 //! # });
 //! ```
 
+use core::cell::UnsafeCell;
 use core::fmt;
 #[doc(no_inline)]
 pub use core::future::Future;
 use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
 use core::task::{Context, Poll};
 
 pub use futures_micro::{
@@ -31,6 +33,12 @@ use pin_project_lite::pin_project;
 extern crate alloc;
 #[cfg(not(feature = "std"))]
 use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::task::Waker;
 
 #[cfg(feature = "std")]
 use parking::Parker;
@@ -40,6 +48,8 @@ use waker_fn::waker_fn;
 use std::task::Waker;
 #[cfg(feature = "std")]
 use std::cell::RefCell;
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
 #[cfg(feature = "std")]
 use crate::pin;
@@ -496,6 +506,993 @@ pub trait FutureExt: Future {
     {
         Box::pin(self)
     }
+
+    /// Turns this future into a future that can be cloned and polled from many places,
+    /// with every clone receiving a clone of the single computed output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_lite::*;
+    ///
+    /// # future::block_on(async {
+    /// let shared = future::ready(7).shared();
+    /// let a = shared.clone();
+    /// let b = shared.clone();
+    ///
+    /// assert_eq!(a.await, 7);
+    /// assert_eq!(b.await, 7);
+    /// # })
+    /// ```
+    #[cfg(feature = "std")]
+    fn shared(self) -> Shared<Self>
+    where
+        Self: Sized,
+        Self::Output: Clone,
+    {
+        Shared {
+            inner: Arc::new(std::sync::Mutex::new(SharedState::Polling {
+                future: Box::pin(self),
+                wakers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Catches panics while polling the future, turning them into an `Err` instead of
+    /// unwinding through the caller.
+    ///
+    /// Once the inner future has resolved — whether by completing normally or by
+    /// panicking — it must not be polled again: doing so panics with "`CatchUnwind`
+    /// polled after it already completed or panicked".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_lite::*;
+    ///
+    /// # future::block_on(async {
+    /// let fut = async { panic!("oh no") };
+    /// assert!(fut.catch_unwind().await.is_err());
+    /// # })
+    /// ```
+    #[cfg(feature = "std")]
+    fn catch_unwind(self) -> CatchUnwind<Self>
+    where
+        Self: Sized + std::panic::UnwindSafe,
+    {
+        CatchUnwind {
+            future: Some(self),
+        }
+    }
+
+    /// Maps this future's output with a function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_lite::*;
+    ///
+    /// # future::block_on(async {
+    /// let a = future::ready(1).map(|x| x + 1);
+    /// assert_eq!(a.await, 2);
+    /// # })
+    /// ```
+    fn map<T, Func>(self, f: Func) -> Map<Self, Func>
+    where
+        Self: Sized,
+        Func: FnOnce(Self::Output) -> T,
+    {
+        Map {
+            future: self,
+            f: Some(f),
+        }
+    }
+
+    /// Chains this future with another one that is created from its output once it resolves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_lite::*;
+    ///
+    /// # future::block_on(async {
+    /// let a = future::ready(1).then(|x| future::ready(x + 1));
+    /// assert_eq!(a.await, 2);
+    /// # })
+    /// ```
+    fn then<Fut, Func>(self, f: Func) -> Then<Self, Fut, Func>
+    where
+        Self: Sized,
+        Fut: Future,
+        Func: FnOnce(Self::Output) -> Fut,
+    {
+        Then::First {
+            future: self,
+            f: Some(f),
+        }
+    }
+
+    /// Flattens a future of a future into a single future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_lite::*;
+    ///
+    /// # future::block_on(async {
+    /// let a = future::ready(future::ready(1)).flatten();
+    /// assert_eq!(a.await, 1);
+    /// # })
+    /// ```
+    fn flatten(self) -> Flatten<Self, Self::Output>
+    where
+        Self: Sized,
+        Self::Output: Future,
+    {
+        Flatten::First { future: self }
+    }
+
+    /// Wraps this future in an [`Either::Left`], for branches that return one of two future
+    /// types without boxing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_lite::*;
+    /// use futures_lite::future::{pending, ready};
+    ///
+    /// # future::block_on(async {
+    /// let cond = true;
+    /// let fut = if cond {
+    ///     ready(1).left_future()
+    /// } else {
+    ///     pending().right_future()
+    /// };
+    /// assert_eq!(fut.await, 1);
+    /// # })
+    /// ```
+    fn left_future<R>(self) -> Either<Self, R>
+    where
+        Self: Sized,
+        R: Future<Output = Self::Output>,
+    {
+        Either::Left { left: self }
+    }
+
+    /// Wraps this future in an [`Either::Right`], for branches that return one of two future
+    /// types without boxing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_lite::*;
+    /// use futures_lite::future::{pending, ready};
+    ///
+    /// # future::block_on(async {
+    /// let cond = false;
+    /// let fut = if cond {
+    ///     pending().left_future()
+    /// } else {
+    ///     ready(2).right_future()
+    /// };
+    /// assert_eq!(fut.await, 2);
+    /// # })
+    /// ```
+    fn right_future<L>(self) -> Either<L, Self>
+    where
+        Self: Sized,
+        L: Future<Output = Self::Output>,
+    {
+        Either::Right { right: self }
+    }
+
+    /// Wraps this future so it is safe to keep polling after it completes.
+    ///
+    /// Once the inner future resolves, every later poll returns [`Poll::Pending`] forever
+    /// instead of polling the (now possibly invalid) inner future again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_lite::*;
+    ///
+    /// # future::block_on(async {
+    /// let mut fut = future::ready(1).fuse();
+    /// assert!(!fut.is_terminated());
+    /// assert_eq!((&mut fut).await, 1);
+    /// assert!(fut.is_terminated());
+    /// # })
+    /// ```
+    fn fuse(self) -> Fuse<Self>
+    where
+        Self: Sized,
+    {
+        Fuse { future: Some(self) }
+    }
 }
 
 impl<F: ?Sized> FutureExt for F where F: Future {}
+
+/// An error returned when a future is aborted by an [`AbortHandle`].
+///
+/// # Examples
+///
+/// ```
+/// use futures_lite::future::{abortable, pending};
+///
+/// # futures_lite::future::block_on(async {
+/// let (fut, handle) = abortable(pending::<()>());
+/// handle.abort();
+/// assert_eq!(fut.await, Err(futures_lite::future::Aborted));
+/// # })
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Aborted;
+
+impl fmt::Display for Aborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "future has been aborted")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Aborted {}
+
+/// A slot that holds at most one [`Waker`], protected by a tiny spinlock.
+///
+/// This is used instead of a full mutex so [`abortable()`] keeps working without the `std`
+/// feature.
+struct WakerSlot {
+    locked: AtomicBool,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+// SAFETY: access to `waker` is always guarded by `locked`.
+unsafe impl Send for WakerSlot {}
+unsafe impl Sync for WakerSlot {}
+
+impl WakerSlot {
+    fn new() -> WakerSlot {
+        WakerSlot {
+            locked: AtomicBool::new(false),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    fn lock(&self) {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+    }
+
+    fn unlock(&self) {
+        self.locked.store(false, Ordering::Release);
+    }
+
+    /// Stores `waker` in the slot, replacing any previously registered waker.
+    fn register(&self, waker: &Waker) {
+        self.lock();
+        unsafe { *self.waker.get() = Some(waker.clone()) };
+        self.unlock();
+    }
+
+    /// Takes the registered waker, if any, and wakes it.
+    fn take_and_wake(&self) {
+        self.lock();
+        let waker = unsafe { (*self.waker.get()).take() };
+        self.unlock();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+struct AbortInner {
+    aborted: AtomicBool,
+    waker: WakerSlot,
+}
+
+/// Creates a new abortable future alongside an [`AbortHandle`] that can abort it.
+///
+/// When [`AbortHandle::abort()`] is called, the [`Abortable`] future resolves to
+/// `Err(Aborted)` instead of running `future` to completion. Dropping the handle without
+/// aborting leaves `future` running normally.
+///
+/// # Examples
+///
+/// ```
+/// use futures_lite::future::{abortable, pending};
+///
+/// # futures_lite::future::block_on(async {
+/// let (fut, handle) = abortable(async { 1 + 2 });
+/// assert_eq!(fut.await, Ok(3));
+///
+/// let (fut, handle) = abortable(pending::<()>());
+/// handle.abort();
+/// assert!(fut.await.is_err());
+/// # })
+/// ```
+pub fn abortable<F>(future: F) -> (Abortable<F>, AbortHandle)
+where
+    F: Future,
+{
+    let inner = Arc::new(AbortInner {
+        aborted: AtomicBool::new(false),
+        waker: WakerSlot::new(),
+    });
+    let abortable = Abortable {
+        future,
+        inner: inner.clone(),
+    };
+    let handle = AbortHandle { inner };
+    (abortable, handle)
+}
+
+pin_project! {
+    /// Future for the [`abortable()`] function.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Abortable<F> {
+        #[pin]
+        future: F,
+        inner: Arc<AbortInner>,
+    }
+}
+
+impl<F> fmt::Debug for Abortable<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Abortable").finish()
+    }
+}
+
+impl<F: Future> Future for Abortable<F> {
+    type Output = Result<F::Output, Aborted>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if this.inner.aborted.load(Ordering::Acquire) {
+            return Poll::Ready(Err(Aborted));
+        }
+
+        match this.future.poll(cx) {
+            Poll::Ready(t) => Poll::Ready(Ok(t)),
+            Poll::Pending => {
+                this.inner.waker.register(cx.waker());
+
+                // Re-check after registering the waker so we don't miss an abort that raced
+                // with the registration above.
+                if this.inner.aborted.load(Ordering::Acquire) {
+                    Poll::Ready(Err(Aborted))
+                } else {
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+/// A handle that can remotely abort an [`Abortable`] future.
+///
+/// Dropping the handle without calling [`abort()`][`AbortHandle::abort()`] has no effect on
+/// the associated future, which keeps running normally.
+#[derive(Clone, Debug)]
+pub struct AbortHandle {
+    inner: Arc<AbortInner>,
+}
+
+impl AbortHandle {
+    /// Aborts the associated [`Abortable`] future.
+    ///
+    /// If the future is currently being polled elsewhere, it is woken up so it can notice the
+    /// abort and resolve to `Err(Aborted)`.
+    pub fn abort(&self) {
+        self.inner.aborted.store(true, Ordering::Release);
+        self.inner.waker.take_and_wake();
+    }
+}
+
+impl fmt::Debug for AbortInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AbortInner").finish()
+    }
+}
+
+/// Waits for the first future in `futures` that resolves to `Ok`, discarding the rest.
+///
+/// If every future resolves to `Err`, the error of the last one to complete is returned.
+///
+/// # Examples
+///
+/// ```
+/// use futures_lite::future::{ready, select_ok};
+///
+/// # futures_lite::future::block_on(async {
+/// let futures = vec![
+///     ready(Err::<i32, i32>(1)),
+///     ready(Ok::<i32, i32>(2)),
+///     ready(Err::<i32, i32>(3)),
+/// ];
+/// assert_eq!(select_ok(futures).await, Ok(2));
+///
+/// let futures = vec![ready(Err::<i32, i32>(1)), ready(Err::<i32, i32>(2))];
+/// assert_eq!(select_ok(futures).await, Err(2));
+/// # })
+/// ```
+///
+/// # Panics
+///
+/// This function panics if the iterator is empty.
+pub fn select_ok<F, T, E>(futures: impl IntoIterator<Item = F>) -> SelectOk<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let futures: Vec<Pin<Box<F>>> = futures.into_iter().map(Box::pin).collect();
+    assert!(!futures.is_empty(), "`select_ok()` was called with an empty iterator");
+    SelectOk { futures }
+}
+
+/// Future for the [`select_ok()`] function.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SelectOk<F> {
+    futures: Vec<Pin<Box<F>>>,
+}
+
+impl<F> fmt::Debug for SelectOk<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectOk").finish()
+    }
+}
+
+impl<F, T, E> Future for SelectOk<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    type Output = Result<T, E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut last_err = None;
+
+        let mut i = 0;
+        while i < self.futures.len() {
+            match self.futures[i].as_mut().poll(cx) {
+                Poll::Ready(Ok(t)) => return Poll::Ready(Ok(t)),
+                Poll::Ready(Err(err)) => {
+                    self.futures.swap_remove(i);
+                    last_err = Some(err);
+                }
+                Poll::Pending => i += 1,
+            }
+        }
+
+        match last_err {
+            Some(err) if self.futures.is_empty() => Poll::Ready(Err(err)),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+/// The state of a single slot inside [`JoinAll`] or [`TryJoinAll`].
+enum MaybeDone<F: Future> {
+    /// The inner future is still running.
+    Future(Pin<Box<F>>),
+    /// The inner future has completed and its output is stored here.
+    Done(F::Output),
+    /// The output has already been taken out.
+    Taken,
+}
+
+impl<F: Future> MaybeDone<F> {
+    /// Polls the inner future if it hasn't completed yet.
+    fn poll(&mut self, cx: &mut Context<'_>) {
+        if let MaybeDone::Future(fut) = self {
+            if let Poll::Ready(t) = fut.as_mut().poll(cx) {
+                *self = MaybeDone::Done(t);
+            }
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        !matches!(self, MaybeDone::Future(_))
+    }
+
+    fn take_output(&mut self) -> F::Output {
+        match core::mem::replace(self, MaybeDone::Taken) {
+            MaybeDone::Done(t) => t,
+            _ => unreachable!("`MaybeDone::take_output()` called on an already-taken slot"),
+        }
+    }
+}
+
+/// Waits for a collection of futures to complete, preserving the input order in the output.
+///
+/// # Examples
+///
+/// ```
+/// use futures_lite::future::join_all;
+///
+/// # futures_lite::future::block_on(async {
+/// let futures = vec![async { 1 }, async { 2 }, async { 3 }];
+/// assert_eq!(join_all(futures).await, vec![1, 2, 3]);
+/// # })
+/// ```
+pub fn join_all<F: Future>(futures: impl IntoIterator<Item = F>) -> JoinAll<F> {
+    let elems = futures
+        .into_iter()
+        .map(|f| MaybeDone::Future(Box::pin(f)))
+        .collect();
+    JoinAll { elems }
+}
+
+/// Future for the [`join_all()`] function.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct JoinAll<F: Future> {
+    elems: Box<[MaybeDone<F>]>,
+}
+
+impl<F: Future> fmt::Debug for JoinAll<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinAll").finish()
+    }
+}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut all_done = true;
+
+        for elem in self.elems.iter_mut() {
+            elem.poll(cx);
+            if !elem.is_done() {
+                all_done = false;
+            }
+        }
+
+        if all_done {
+            let out = self.elems.iter_mut().map(MaybeDone::take_output).collect();
+            Poll::Ready(out)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Waits for a collection of fallible futures to complete, short-circuiting on the first error.
+///
+/// # Examples
+///
+/// ```
+/// use futures_lite::future::try_join_all;
+///
+/// # futures_lite::future::block_on(async {
+/// let futures = vec![
+///     async { Ok::<i32, i32>(1) },
+///     async { Ok::<i32, i32>(2) },
+/// ];
+/// assert_eq!(try_join_all(futures).await, Ok(vec![1, 2]));
+///
+/// let futures = vec![async { Ok::<i32, i32>(1) }, async { Err::<i32, i32>(2) }];
+/// assert_eq!(try_join_all(futures).await, Err(2));
+/// # })
+/// ```
+pub fn try_join_all<F, T, E>(futures: impl IntoIterator<Item = F>) -> TryJoinAll<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let elems = futures
+        .into_iter()
+        .map(|f| MaybeDone::Future(Box::pin(f)))
+        .collect();
+    TryJoinAll { elems }
+}
+
+/// Future for the [`try_join_all()`] function.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TryJoinAll<F: Future> {
+    elems: Box<[MaybeDone<F>]>,
+}
+
+impl<F: Future> fmt::Debug for TryJoinAll<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryJoinAll").finish()
+    }
+}
+
+impl<F, T, E> Future for TryJoinAll<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Vec<T>, E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut all_done = true;
+
+        for elem in self.elems.iter_mut() {
+            elem.poll(cx);
+            match elem {
+                MaybeDone::Done(Err(_)) => {
+                    let err = match core::mem::replace(elem, MaybeDone::Taken) {
+                        MaybeDone::Done(Err(err)) => err,
+                        _ => unreachable!(),
+                    };
+                    return Poll::Ready(Err(err));
+                }
+                MaybeDone::Done(Ok(_)) => {}
+                _ => all_done = false,
+            }
+        }
+
+        if all_done {
+            let out = self
+                .elems
+                .iter_mut()
+                .map(|elem| match elem.take_output() {
+                    Ok(t) => t,
+                    Err(_) => unreachable!("error case is handled above"),
+                })
+                .collect();
+            Poll::Ready(Ok(out))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+enum SharedState<F: Future> {
+    /// The inner future is still running; whichever clone is polled polls it.
+    Polling {
+        future: Pin<Box<F>>,
+        wakers: Vec<Waker>,
+    },
+    /// The inner future has completed; every clone from now on just clones the output.
+    Done(F::Output),
+}
+
+/// Future for the [`shared()`][`FutureExt::shared()`] method.
+///
+/// Cloning a `Shared` does not clone the underlying computation: all clones share the same
+/// future and each one receives a clone of its eventual output.
+///
+/// Note: polling a `Shared` is not reentrant. All clones lock the same mutex, so do not poll
+/// any clone of it from within the future it wraps.
+#[cfg(feature = "std")]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Shared<F: Future> {
+    inner: Arc<std::sync::Mutex<SharedState<F>>>,
+}
+
+#[cfg(feature = "std")]
+impl<F: Future> Clone for Shared<F> {
+    fn clone(&self) -> Self {
+        Shared {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Future> fmt::Debug for Shared<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shared").finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F> Future for Shared<F>
+where
+    F: Future,
+    F::Output: Clone,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.inner.lock().unwrap();
+
+        if let SharedState::Done(output) = &*state {
+            return Poll::Ready(output.clone());
+        }
+
+        let output = if let SharedState::Polling { future, .. } = &mut *state {
+            match future.as_mut().poll(cx) {
+                Poll::Ready(output) => output,
+                Poll::Pending => {
+                    if let SharedState::Polling { wakers, .. } = &mut *state {
+                        if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                            wakers.push(cx.waker().clone());
+                        }
+                    }
+                    return Poll::Pending;
+                }
+            }
+        } else {
+            unreachable!()
+        };
+
+        let wakers = match core::mem::replace(&mut *state, SharedState::Done(output.clone())) {
+            SharedState::Polling { wakers, .. } => wakers,
+            SharedState::Done(_) => unreachable!(),
+        };
+        drop(state);
+
+        for waker in wakers {
+            waker.wake();
+        }
+
+        Poll::Ready(output)
+    }
+}
+
+pin_project! {
+    /// Future for the [`catch_unwind()`][`FutureExt::catch_unwind()`] method.
+    #[cfg(feature = "std")]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct CatchUnwind<F> {
+        #[pin]
+        future: Option<F>,
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F> fmt::Debug for CatchUnwind<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchUnwind").finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F> Future for CatchUnwind<F>
+where
+    F: Future + std::panic::UnwindSafe,
+{
+    type Output = std::thread::Result<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        let fut = this
+            .future
+            .as_mut()
+            .as_pin_mut()
+            .expect("`CatchUnwind` polled after it already completed or panicked");
+
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| fut.poll(cx))) {
+            Ok(Poll::Ready(v)) => {
+                this.future.set(None);
+                Poll::Ready(Ok(v))
+            }
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => {
+                this.future.set(None);
+                Poll::Ready(Err(payload))
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// Future for the [`map()`][`FutureExt::map()`] method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Map<F, Func> {
+        #[pin]
+        future: F,
+        f: Option<Func>,
+    }
+}
+
+impl<F, Func> fmt::Debug for Map<F, Func> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map").finish()
+    }
+}
+
+impl<F, Func, T> Future for Map<F, Func>
+where
+    F: Future,
+    Func: FnOnce(F::Output) -> T,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        match this.future.poll(cx) {
+            Poll::Ready(v) => {
+                let f = this.f.take().expect("`Map` polled after completion");
+                Poll::Ready(f(v))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+pin_project! {
+    /// Future for the [`then()`][`FutureExt::then()`] method.
+    #[project = ThenProj]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub enum Then<F1, F2, Func> {
+        First {
+            #[pin]
+            future: F1,
+            f: Option<Func>,
+        },
+        Second {
+            #[pin]
+            future: F2,
+        },
+        Done,
+    }
+}
+
+impl<F1, F2, Func> fmt::Debug for Then<F1, F2, Func> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Then").finish()
+    }
+}
+
+impl<F1, F2, Func> Future for Then<F1, F2, Func>
+where
+    F1: Future,
+    F2: Future,
+    Func: FnOnce(F1::Output) -> F2,
+{
+    type Output = F2::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let ThenProj::First { future, f } = self.as_mut().project() {
+            match future.poll(cx) {
+                Poll::Ready(v) => {
+                    let f = f.take().expect("`Then` polled after completion");
+                    self.as_mut().set(Then::Second { future: f(v) });
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let out = match self.as_mut().project() {
+            ThenProj::Second { future } => match future.poll(cx) {
+                Poll::Ready(v) => v,
+                Poll::Pending => return Poll::Pending,
+            },
+            ThenProj::Done => panic!("`Then` polled after completion"),
+            ThenProj::First { .. } => unreachable!("transitioned out of `First` above"),
+        };
+        self.set(Then::Done);
+        Poll::Ready(out)
+    }
+}
+
+pin_project! {
+    /// Future for the [`flatten()`][`FutureExt::flatten()`] method.
+    #[project = FlattenProj]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub enum Flatten<F1, F2> {
+        First {
+            #[pin]
+            future: F1,
+        },
+        Second {
+            #[pin]
+            future: F2,
+        },
+        Done,
+    }
+}
+
+impl<F1, F2> fmt::Debug for Flatten<F1, F2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Flatten").finish()
+    }
+}
+
+impl<F1> Future for Flatten<F1, F1::Output>
+where
+    F1: Future,
+    F1::Output: Future,
+{
+    type Output = <F1::Output as Future>::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let FlattenProj::First { future } = self.as_mut().project() {
+            match future.poll(cx) {
+                Poll::Ready(inner) => self.as_mut().set(Flatten::Second { future: inner }),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let out = match self.as_mut().project() {
+            FlattenProj::Second { future } => match future.poll(cx) {
+                Poll::Ready(v) => v,
+                Poll::Pending => return Poll::Pending,
+            },
+            FlattenProj::Done => panic!("`Flatten` polled after completion"),
+            FlattenProj::First { .. } => unreachable!("transitioned out of `First` above"),
+        };
+        self.set(Flatten::Done);
+        Poll::Ready(out)
+    }
+}
+
+pin_project! {
+    /// A future that is either one of two concrete future types.
+    ///
+    /// This lets an `if`/`match` branch return one of two different future types without
+    /// paying for a [`boxed()`][`FutureExt::boxed()`] allocation, as long as both arms share
+    /// the same [`Output`][`Future::Output`].
+    #[project = EitherProj]
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub enum Either<L, R> {
+        /// A future of the first type.
+        Left {
+            #[pin]
+            left: L,
+        },
+        /// A future of the second type.
+        Right {
+            #[pin]
+            right: R,
+        },
+    }
+}
+
+impl<L, R, T> Future for Either<L, R>
+where
+    L: Future<Output = T>,
+    R: Future<Output = T>,
+{
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            EitherProj::Left { left } => left.poll(cx),
+            EitherProj::Right { right } => right.poll(cx),
+        }
+    }
+}
+
+pin_project! {
+    /// Future for the [`fuse()`][`FutureExt::fuse()`] method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Fuse<F> {
+        #[pin]
+        future: Option<F>,
+    }
+}
+
+impl<F> fmt::Debug for Fuse<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fuse").finish()
+    }
+}
+
+impl<F> Fuse<F> {
+    /// Returns `true` if the inner future has already completed.
+    ///
+    /// Combinators that select over several futures (such as `select_ok()` and `join_all()`)
+    /// can use this to skip futures that are already done.
+    pub fn is_terminated(&self) -> bool {
+        self.future.is_none()
+    }
+}
+
+impl<F: Future> Future for Fuse<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        match this.future.as_mut().as_pin_mut() {
+            Some(fut) => match fut.poll(cx) {
+                Poll::Ready(v) => {
+                    this.future.set(None);
+                    Poll::Ready(v)
+                }
+                Poll::Pending => Poll::Pending,
+            },
+            None => Poll::Pending,
+        }
+    }
+}
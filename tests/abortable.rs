@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use futures_lite::future::{abortable, poll_fn, Aborted, Future};
+
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// An `abort()` that arrives while the future is pending must not be lost: it has to wake the
+// waker registered by the pending poll, and the next poll has to resolve to `Err(Aborted)`.
+// This drives that sequence (poll, abort, poll) instead of only the "abort before first poll"
+// and "never abort" cases covered by the doctests.
+#[test]
+fn abort_after_pending_wakes_the_registered_waker() {
+    let waker = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let cx_waker = Waker::from(waker.clone());
+    let mut cx = Context::from_waker(&cx_waker);
+
+    let (fut, handle) = abortable(poll_fn(|_| Poll::<()>::Pending));
+    let mut fut = Box::pin(fut);
+
+    // First poll: the inner future is pending, so `Abortable` registers our waker.
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(waker.0.load(Ordering::SeqCst), 0);
+
+    // Aborting now must wake the waker that was just registered.
+    handle.abort();
+    assert_eq!(waker.0.load(Ordering::SeqCst), 1);
+
+    // The next poll must observe the abort instead of polling the inner future again.
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Err(Aborted)));
+}
@@ -0,0 +1,77 @@
+use std::cell::Cell;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use futures_lite::future::{join_all, poll_fn, try_join_all, Future};
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+    fn wake_by_ref(self: &Arc<Self>) {}
+}
+
+fn noop_cx_waker() -> Waker {
+    Waker::from(Arc::new(NoopWaker))
+}
+
+fn pending_until(count: u32, value: i32) -> Pin<Box<dyn Future<Output = i32>>> {
+    let polls = Cell::new(0u32);
+    Box::pin(poll_fn(move |_| {
+        polls.set(polls.get() + 1);
+        if polls.get() < count {
+            Poll::Pending
+        } else {
+            Poll::Ready(value)
+        }
+    }))
+}
+
+// `JoinAll` must preserve input order in its output whatever the completion order (its own
+// doc comment says as much), but every doctest only uses futures that all complete on
+// the very first poll, so input order and completion order always coincide — the reordering
+// logic is never actually exercised. Here the *last* future completes on the first poll while
+// the *first* future needs three polls, so completion order is reversed relative to input order.
+#[test]
+fn join_all_preserves_input_order_when_futures_complete_out_of_order() {
+    let futures = vec![pending_until(3, 1), pending_until(2, 2), pending_until(1, 3)];
+    let mut fut = Box::pin(join_all(futures));
+    let waker = noop_cx_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(vec![1, 2, 3]));
+}
+
+fn pending_until_result(
+    count: u32,
+    value: Result<i32, i32>,
+) -> Pin<Box<dyn Future<Output = Result<i32, i32>>>> {
+    let polls = Cell::new(0u32);
+    Box::pin(poll_fn(move |_| {
+        polls.set(polls.get() + 1);
+        if polls.get() < count {
+            Poll::Pending
+        } else {
+            Poll::Ready(value)
+        }
+    }))
+}
+
+#[test]
+fn try_join_all_preserves_input_order_when_futures_complete_out_of_order() {
+    let futures = vec![
+        pending_until_result(3, Ok(1)),
+        pending_until_result(2, Ok(2)),
+        pending_until_result(1, Ok(3)),
+    ];
+    let mut fut = Box::pin(try_join_all(futures));
+    let waker = noop_cx_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(vec![1, 2, 3])));
+}
@@ -0,0 +1,68 @@
+use std::cell::Cell;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use futures_lite::future::{poll_fn, select_ok, Future};
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+    fn wake_by_ref(self: &Arc<Self>) {}
+}
+
+fn noop_cx_waker() -> Waker {
+    Waker::from(Arc::new(NoopWaker))
+}
+
+type BoxFuture = Pin<Box<dyn Future<Output = Result<i32, i32>>>>;
+
+fn pending_then(count: u32, value: Result<i32, i32>) -> BoxFuture {
+    let polls = Cell::new(0u32);
+    Box::pin(poll_fn(move |_| {
+        polls.set(polls.get() + 1);
+        if polls.get() < count {
+            Poll::Pending
+        } else {
+            Poll::Ready(value)
+        }
+    }))
+}
+
+// `select_ok()`'s poll loop swap-removes a future from its `Vec` the moment it errors, then
+// keeps iterating from the same index — get that wrong and it either skips the future that
+// slid into the removed slot or panics on an out-of-bounds access. This drives three futures
+// that resolve `Err`/`Err`/`Ok` across three separate poll calls (never all at once), so the
+// vector is shrinking while later futures are still pending.
+#[test]
+fn resolves_ok_after_other_futures_error_across_polls() {
+    let futures = vec![
+        pending_then(2, Err(1)),
+        pending_then(3, Err(2)),
+        pending_then(4, Ok(42)),
+    ];
+    let mut fut = Box::pin(select_ok(futures));
+    let waker = noop_cx_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending); // first future errors, is removed
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending); // second future errors, is removed
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(42))); // third future wins
+}
+
+// When every future ultimately errors, `select_ok()` must report the error of whichever one
+// resolved *last*, not the first. Stagger the resolutions across polls so this can't be
+// satisfied by accident (e.g. by only ever keeping the first error seen).
+#[test]
+fn reports_the_last_error_when_all_futures_fail_across_polls() {
+    let futures = vec![pending_then(2, Err(10)), pending_then(3, Err(20))];
+    let mut fut = Box::pin(select_ok(futures));
+    let waker = noop_cx_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending); // first future errors, is removed
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Err(20))); // second future errors last
+}
@@ -0,0 +1,65 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use futures_lite::future::{poll_fn, Future, FutureExt};
+
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// `Shared`'s waiter list de-duplicates wakers with `Waker::will_wake` before pushing, so
+// polling the same clone with the same waker twice (e.g. an executor re-polling a task that's
+// still pending) must not register it a second time and later wake it twice. This drives two
+// clones, each polled from its own waker — one of them twice with an unchanged waker — and
+// checks every distinct waker fires exactly once when the inner future resolves.
+#[test]
+fn shared_wakes_each_distinct_waiter_exactly_once() {
+    let polls = Cell::new(0u32);
+    let shared = poll_fn(move |_| {
+        polls.set(polls.get() + 1);
+        if polls.get() < 4 {
+            Poll::Pending
+        } else {
+            Poll::Ready(7)
+        }
+    })
+    .shared();
+
+    let waker_a = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let cx_waker_a = Waker::from(waker_a.clone());
+    let mut cx_a = Context::from_waker(&cx_waker_a);
+
+    let waker_b = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let cx_waker_b = Waker::from(waker_b.clone());
+    let mut cx_b = Context::from_waker(&cx_waker_b);
+
+    let mut first = Box::pin(shared.clone());
+    let mut second = Box::pin(shared);
+
+    // Poll `first` twice with the same waker: it must only be registered once.
+    assert_eq!(first.as_mut().poll(&mut cx_a), Poll::Pending);
+    assert_eq!(first.as_mut().poll(&mut cx_a), Poll::Pending);
+
+    // Poll `second` with a different waker.
+    assert_eq!(second.as_mut().poll(&mut cx_b), Poll::Pending);
+
+    // The fourth poll (on either clone) drives the inner future to completion and must wake
+    // every distinct registered waker exactly once.
+    assert_eq!(first.as_mut().poll(&mut cx_a), Poll::Ready(7));
+    assert_eq!(waker_a.0.load(Ordering::SeqCst), 1);
+    assert_eq!(waker_b.0.load(Ordering::SeqCst), 1);
+
+    // Once done, every clone just clones the cached output without touching wakers again.
+    assert_eq!(second.as_mut().poll(&mut cx_b), Poll::Ready(7));
+    assert_eq!(waker_b.0.load(Ordering::SeqCst), 1);
+}